@@ -0,0 +1,142 @@
+//! `EnvFilter`-style per-target level directives.
+//!
+//! Both logging and tracing settings accept a directive string parsed like
+//! `tracing-subscriber`'s `EnvFilter`: comma-separated `target=level` entries plus an
+//! optional bare `level` that sets the global default. At each callsite the most
+//! specific directive whose target is a prefix of the event's module path wins,
+//! falling back to the global default.
+//!
+//! The parsed directives live behind a [`FilterHandle`] so operators can retune
+//! verbosity at runtime without restarting. [`telemetry::init`] parses
+//! `TelemetrySettings`'s directive strings via `build_filter` and passes the
+//! resulting handle to `log::init::init`/`tracing::init::init`, which install it and
+//! keep a clone for later [`FilterHandle::reload`] calls.
+//!
+//! Consulting the handle from each log/span callsite is `log::internal`'s and
+//! `tracing::internal`'s job respectively (that's where events and spans are actually
+//! emitted); this module only owns parsing and the swappable handle.
+//!
+//! [`telemetry::init`]: super::init
+
+use parking_lot::RwLock;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Verbosity level, ordered from least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" | "warning" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            other => Err(ParseError::UnknownLevel(other.to_string())),
+        }
+    }
+}
+
+/// Error returned when a directive string can't be parsed.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    /// A directive named a level that isn't recognized.
+    #[error("unknown level `{0}`")]
+    UnknownLevel(String),
+}
+
+/// A set of `target=level` directives with a global default, resolved most-specific-first.
+#[derive(Debug, Clone)]
+pub struct Directives {
+    default: Level,
+
+    // Sorted by descending target length so the first prefix match is the most specific.
+    targets: Vec<(String, Level)>,
+}
+
+impl Directives {
+    /// Parse a comma-separated directive string such as `my_crate::db=debug,hyper=warn,info`.
+    ///
+    /// A bare entry (no `=`) sets the global default; the last one wins. When no bare
+    /// entry is present the default stays at [`Level::Info`].
+    pub fn parse(directives: &str) -> Result<Self, ParseError> {
+        let mut default = Level::Info;
+        let mut targets = Vec::new();
+
+        for entry in directives.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            match entry.split_once('=') {
+                Some((target, level)) => targets.push((target.trim().to_string(), level.parse()?)),
+                None => default = entry.parse()?,
+            }
+        }
+
+        targets.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        Ok(Self { default, targets })
+    }
+
+    /// Select the level for `module_path`: the most specific matching directive, else the default.
+    pub fn level_for(&self, module_path: &str) -> Level {
+        self.targets
+            .iter()
+            .find(|(target, _)| module_path.starts_with(target.as_str()))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// Whether an event at `level` from `module_path` should be emitted.
+    pub fn enabled(&self, module_path: &str, level: Level) -> bool {
+        level <= self.level_for(module_path)
+    }
+}
+
+impl Default for Directives {
+    fn default() -> Self {
+        Self {
+            default: Level::Info,
+            targets: Vec::new(),
+        }
+    }
+}
+
+/// Swappable handle to the active [`Directives`], installed by `init` and shared with
+/// operators for runtime retuning.
+#[derive(Debug, Clone)]
+pub struct FilterHandle {
+    directives: Arc<RwLock<Directives>>,
+}
+
+impl FilterHandle {
+    /// Install `directives` behind a fresh handle.
+    pub fn new(directives: Directives) -> Self {
+        Self {
+            directives: Arc::new(RwLock::new(directives)),
+        }
+    }
+
+    /// Whether an event at `level` from `module_path` should be emitted.
+    pub fn enabled(&self, module_path: &str, level: Level) -> bool {
+        self.directives.read().enabled(module_path, level)
+    }
+
+    /// Atomically replace the active directives from a new directive string.
+    pub fn reload(&self, directives: &str) -> Result<(), ParseError> {
+        *self.directives.write() = Directives::parse(directives)?;
+        Ok(())
+    }
+}
@@ -3,6 +3,9 @@
 #[cfg(any(feature = "logging", feature = "tracing"))]
 mod scope;
 
+#[cfg(any(feature = "logging", feature = "tracing"))]
+pub mod filter;
+
 #[cfg(feature = "testing")]
 mod testing;
 
@@ -12,15 +15,25 @@ pub mod log;
 #[cfg(feature = "tracing")]
 pub mod tracing;
 
+#[cfg(all(feature = "tracing", feature = "tracing-otlp"))]
+mod otlp;
+
+#[cfg(all(feature = "tower", feature = "logging", feature = "tracing"))]
+pub mod tower;
+
 pub mod settings;
 
 use self::settings::TelemetrySettings;
 use crate::utils::feature_use;
 use crate::{BootstrapResult, ServiceInfo};
+use futures_util::{Sink, Stream};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+#[cfg(all(feature = "tracing", feature = "tracing-otlp"))]
+use self::settings::TracingExporter;
+
 #[cfg(feature = "testing")]
 pub use self::testing::TestTelemetryScope;
 
@@ -44,7 +57,10 @@ pub struct WithTelemetryContext<'f, T> {
     // NOTE: we intentionally erase type here as we can get close to the type
     // length limit, adding telemetry wrappers on top causes compiler to fail in some
     // cases.
-    inner: Pin<Box<dyn Future<Output = T> + Send + 'f>>,
+    // NOTE: wrapped in an `Option` so `Drop` can drop the inner future *while* the
+    // telemetry scope is active; the field itself is only dropped once, after the
+    // manual `Drop` impl has already taken it.
+    inner: Option<Pin<Box<dyn Future<Output = T> + Send + 'f>>>,
     ctx: TelemetryContext,
 }
 
@@ -54,7 +70,77 @@ impl<'f, T> Future for WithTelemetryContext<'f, T> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let _telemetry_scope = self.ctx.scope();
 
-        self.inner.as_mut().poll(cx)
+        self.inner
+            .as_mut()
+            .expect("inner future is only taken in `Drop`")
+            .as_mut()
+            .poll(cx)
+    }
+}
+
+impl<T> Drop for WithTelemetryContext<'_, T> {
+    fn drop(&mut self) {
+        // Enter the telemetry scope while the inner future is dropped so that a future
+        // cancelled before completion (task abort, timeout, `select!` loser) still runs
+        // its destructors in the right context: tracing spans record their end and any
+        // `Drop`-side logging lands in the correct log.
+        let _telemetry_scope = self.ctx.scope();
+
+        drop(self.inner.take());
+    }
+}
+
+/// Wrapper for a stream that provides it with [`TelemetryContext`].
+pub struct WithTelemetryContextStream<'s, T> {
+    // NOTE: type is erased for the same reason as in [`WithTelemetryContext`].
+    inner: Pin<Box<dyn Stream<Item = T> + Send + 's>>,
+    ctx: TelemetryContext,
+}
+
+impl<'s, T> Stream for WithTelemetryContextStream<'s, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // NOTE: a stream is polled many times over its lifetime, so we re-enter the
+        // scope on every poll rather than holding it across `.await` points.
+        let _telemetry_scope = self.ctx.scope();
+
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// Wrapper for a sink that provides it with [`TelemetryContext`].
+pub struct WithTelemetryContextSink<'s, Item, E> {
+    // NOTE: type is erased for the same reason as in [`WithTelemetryContext`].
+    inner: Pin<Box<dyn Sink<Item, Error = E> + Send + 's>>,
+    ctx: TelemetryContext,
+}
+
+impl<'s, Item, E> Sink<Item> for WithTelemetryContextSink<'s, Item, E> {
+    type Error = E;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let _telemetry_scope = self.ctx.scope();
+
+        self.inner.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        let _telemetry_scope = self.ctx.scope();
+
+        self.inner.as_mut().start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let _telemetry_scope = self.ctx.scope();
+
+        self.inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let _telemetry_scope = self.ctx.scope();
+
+        self.inner.as_mut().poll_close(cx)
     }
 }
 
@@ -129,7 +215,32 @@ impl TelemetryContext {
         F: Future + Send + 'f,
     {
         WithTelemetryContext {
-            inner: Box::pin(fut),
+            inner: Some(Box::pin(fut)),
+            ctx: self,
+        }
+    }
+
+    /// [`TODO ROCK-13`]
+    pub fn apply_to_stream<'s, S>(self, stream: S) -> WithTelemetryContextStream<'s, S::Item>
+    where
+        S: Stream + Send + 's,
+    {
+        WithTelemetryContextStream {
+            inner: Box::pin(stream),
+            ctx: self,
+        }
+    }
+
+    /// [`TODO ROCK-13`]
+    pub fn apply_to_sink<'s, Item, S>(
+        self,
+        sink: S,
+    ) -> WithTelemetryContextSink<'s, Item, S::Error>
+    where
+        S: Sink<Item> + Send + 's,
+    {
+        WithTelemetryContextSink {
+            inner: Box::pin(sink),
             ctx: self,
         }
     }
@@ -177,15 +288,128 @@ impl TelemetryContext {
     pub fn slog_logger(&self) -> parking_lot::RwLockReadGuard<Logger> {
         self.log.read()
     }
+
+    /// Extend this context's log with additional key-value fields, in place.
+    ///
+    /// Unlike [`with_forked_log`], this mutates the log already held by `self`
+    /// rather than forking a new one, so it's the natural way to seed a just-forked
+    /// log with request-scoped fields before handing the context off, e.g. from a
+    /// [`tower`] `make_span` closure (see [`tower::TelemetryLayer`]).
+    ///
+    /// [`with_forked_log`]: Self::with_forked_log
+    /// [`tower`]: mod@tower
+    pub fn add_log_fields<T>(&self, fields: slog::OwnedKV<T>)
+    where
+        T: slog::SendSyncRefUnwindSafeKV + 'static,
+    {
+        let mut log = self.log.write();
+        *log = log.new(fields);
+    }
 }
 
 /// [`TODO ROCK-13`]
 pub fn init(service_info: ServiceInfo, settings: &TelemetrySettings) -> BootstrapResult<()> {
     #[cfg(feature = "logging")]
-    self::log::init::init(service_info, &settings.logging)?;
+    {
+        // Parsed once here (rather than inside `log::init::init`) so a malformed
+        // directive string fails startup immediately instead of silently falling
+        // back to the default level.
+        let log_filter = settings.logging.build_filter()?;
+        self::log::init::init(service_info, &settings.logging, log_filter)?;
+    }
 
     #[cfg(feature = "tracing")]
-    self::tracing::init::init(service_info, &settings.tracing)?;
+    {
+        let span_filter = settings.tracing.build_filter()?;
+
+        // Match the configured backend and build the reporter it selects; the
+        // default reporter is still built by `tracing::init::init` itself, same as
+        // before this backend existed.
+        #[cfg(feature = "tracing-otlp")]
+        {
+            let otlp_exporter = match &settings.tracing.exporter {
+                TracingExporter::Default => None,
+                TracingExporter::Otlp(otlp_settings) => Some(self::otlp::init(otlp_settings)?),
+            };
+
+            self::tracing::init::init(service_info, &settings.tracing, span_filter, otlp_exporter)?;
+        }
+
+        #[cfg(not(feature = "tracing-otlp"))]
+        self::tracing::init::init(service_info, &settings.tracing, span_filter)?;
+    }
 
     Ok(())
 }
+
+#[cfg(all(test, feature = "testing", feature = "logging", feature = "tracing"))]
+mod tests {
+    use super::*;
+
+    // Regression test for `WithTelemetryContext`'s `Drop` impl: a future cancelled
+    // before completion (task abort, timeout, `select!` loser) must still run its
+    // destructors with the telemetry scope active, not just while it was polled.
+    #[test]
+    fn scope_is_entered_while_a_cancelled_future_is_dropped() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // Owned by the inner future; records whether the forked log is the current
+        // log at the moment the inner future is dropped.
+        struct DropProbe {
+            log: SharedLog,
+            log_in_scope: Arc<AtomicBool>,
+        }
+
+        impl Drop for DropProbe {
+            fn drop(&mut self) {
+                let in_scope = Arc::ptr_eq(&current_log(), &self.log);
+                self.log_in_scope.store(in_scope, Ordering::SeqCst);
+            }
+        }
+
+        let test_scope = TelemetryContext::test();
+
+        let ctx = TelemetryContext::current().with_forked_log();
+        let probe = ctx.clone();
+        let log_in_scope = Arc::new(AtomicBool::new(false));
+        let drop_probe = DropProbe {
+            log: Arc::clone(&ctx.log),
+            log_in_scope: Arc::clone(&log_in_scope),
+        };
+
+        let mut fut = Box::pin(ctx.apply_with_tracing_span(
+            "cancelled_span",
+            std::future::poll_fn(move |_| {
+                let _ = &drop_probe;
+                slog::info!(probe.slog_logger(), "work started");
+                Poll::<()>::Pending
+            }),
+        ));
+
+        let waker = futures_util::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // Poll once so the span opens and the log line above fires, then drop the
+        // future before it resolves, as task cancellation would.
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        drop(fut);
+
+        assert!(
+            log_in_scope.load(Ordering::SeqCst),
+            "the future's log should be the current log while its destructors run"
+        );
+
+        let records = test_scope.log_records();
+        assert!(
+            records.iter().any(|record| record.msg == "work started"),
+            "log fields from the cancelled future should still be present at teardown"
+        );
+
+        let spans = test_scope.finished_spans();
+        let span = spans
+            .iter()
+            .find(|span| span.operation_name() == "cancelled_span")
+            .expect("span opened by the cancelled future should be closed by `Drop`, not lost");
+        assert!(span.is_finished());
+    }
+}
@@ -0,0 +1,235 @@
+//! OpenTelemetry/OTLP export backend for the [`tracing`] subsystem.
+//!
+//! This is an alternative to the crate's default reporter, selectable through
+//! [`TelemetrySettings::tracing`]'s [`TracingExporter::Otlp`]. Spans finished by the
+//! `SharedSpan`/`SpanScope` machinery are translated from `rustracing` span data into
+//! OpenTelemetry span data, batched, and shipped over OTLP/gRPC (via `tonic`) to a
+//! collector endpoint. The OTLP/HTTP transport is not supported.
+//!
+//! Targets the `opentelemetry` 0.18 / `opentelemetry-otlp` 0.11 API surface.
+//!
+//! [`telemetry::init`] matches [`TracingExporter::Otlp`] and, when selected, builds
+//! the [`Exporter`] via [`init`] and passes it to [`tracing::init::init`] to install
+//! in place of the default reporter. Reporting never blocks the caller: `report`
+//! hands the translated span to an unbounded channel, and a dedicated background task
+//! (see [`drain`]) owns the real `opentelemetry_otlp` exporter and does the actual
+//! batching and network I/O.
+//!
+//! [`telemetry::init`]: super::init
+//! [`TelemetrySettings::tracing`]: super::settings::TelemetrySettings
+//! [`TracingExporter::Otlp`]: super::settings::TracingExporter::Otlp
+//! [`tracing::init::init`]: super::tracing::init::init
+
+use super::tracing::internal::FinishedSpan;
+use crate::BootstrapResult;
+use opentelemetry::sdk::export::trace::{SpanData, SpanExporter as _};
+use opentelemetry::sdk::trace::{EvictedHashMap, EvictedQueue};
+use opentelemetry::trace::{SpanContext, SpanId, SpanKind, Status, TraceFlags, TraceId, TraceState};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::SpanExporter;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+
+/// Settings for the OTLP trace export backend.
+#[derive(Clone, Debug)]
+pub struct OtlpSettings {
+    /// OTLP collector endpoint (e.g. `http://127.0.0.1:4317`).
+    pub endpoint: String,
+
+    /// Maximum number of spans buffered before a batch is flushed.
+    pub max_batch_size: usize,
+
+    /// Maximum time a span may wait in the buffer before being flushed.
+    pub batch_timeout: Duration,
+}
+
+impl Default for OtlpSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://127.0.0.1:4317".into(),
+            max_batch_size: 512,
+            batch_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Translate a finished `rustracing` span into OpenTelemetry [`SpanData`].
+///
+/// Carries over the identifiers (trace id, span id, parent span id), the start and
+/// end timestamps, the span tags as attributes, and the span status derived from
+/// the conventional `error` tag.
+pub(crate) fn to_otlp_span(span: &FinishedSpan) -> SpanData {
+    let state = span.context().state();
+
+    let parent_span_id = span
+        .references()
+        .iter()
+        .find(|r| r.is_child_of())
+        .map(|r| SpanId::from_bytes(r.span().span_id().to_be_bytes()))
+        .unwrap_or(SpanId::INVALID);
+
+    let span_context = SpanContext::new(
+        TraceId::from_bytes(state.trace_id().to_be_bytes()),
+        SpanId::from_bytes(state.span_id().to_be_bytes()),
+        TraceFlags::SAMPLED,
+        false,
+        TraceState::default(),
+    );
+
+    let mut is_error = false;
+    let tags: Vec<_> = span.tags().collect();
+    let mut attributes = EvictedHashMap::new(u32::try_from(tags.len()).unwrap_or(u32::MAX), tags.len());
+
+    for tag in &tags {
+        if tag.name() == "error" {
+            is_error = true;
+        }
+        attributes.insert(KeyValue::new(tag.name().to_string(), tag.value().to_string()));
+    }
+
+    SpanData {
+        span_context,
+        parent_span_id,
+        span_kind: SpanKind::Internal,
+        name: span.operation_name().to_string().into(),
+        start_time: span.start_time(),
+        end_time: span.finish_time(),
+        attributes,
+        events: EvictedQueue::new(0),
+        links: EvictedQueue::new(0),
+        status: if is_error { Status::error("") } else { Status::Ok },
+        resource: None,
+        instrumentation_lib: Default::default(),
+    }
+}
+
+/// Build the batching exporter to be installed as the span reporter.
+///
+/// Opens the gRPC channel to `settings.endpoint` once up front and spawns the
+/// [`drain`] task that owns it, rather than dialing (or blocking) on every flush.
+pub(crate) fn init(settings: &OtlpSettings) -> BootstrapResult<Exporter> {
+    let exporter = opentelemetry_otlp::new_exporter()
+        .tonic()
+        .with_endpoint(&settings.endpoint)
+        .build_span_exporter()?;
+
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tokio::runtime::Handle::current().spawn(drain(
+        exporter,
+        rx,
+        settings.max_batch_size,
+        settings.batch_timeout,
+    ));
+
+    Ok(Exporter { tx })
+}
+
+/// Handle to the batching OTLP exporter installed as the `rustracing` span reporter.
+///
+/// Reporting never blocks the reporting task: each finished span is translated and
+/// handed to an unbounded channel drained by a dedicated background task (see
+/// [`drain`]), so a slow or unreachable collector can never stall the task that
+/// finished the span. Cloning shares the same background task and channel.
+#[derive(Clone)]
+pub(crate) struct Exporter {
+    tx: mpsc::UnboundedSender<SpanData>,
+}
+
+impl Exporter {
+    /// Hand a finished span to the background drain task.
+    ///
+    /// Never blocks: this is a plain channel send. If the drain task has already
+    /// shut down (channel closed) the span is silently dropped.
+    pub(crate) fn report(&self, span: &FinishedSpan) {
+        let _ = self.tx.send(to_otlp_span(span));
+    }
+}
+
+/// Background task that batches spans sent over `rx` and ships them to `exporter`.
+///
+/// A batch is flushed whenever it reaches `max_batch_size` or on each tick of a fixed
+/// `batch_timeout` interval, whichever comes first (a size-triggered flush does not
+/// restart the interval), so a span buffered when
+/// traffic stops is still flushed on its own rather than waiting for the next one to
+/// arrive. The final partial batch is flushed once every [`Exporter`] has been
+/// dropped and `rx` closes, so a shutdown doesn't silently drop the tail of a trace.
+async fn drain(
+    mut exporter: SpanExporter,
+    mut rx: mpsc::UnboundedReceiver<SpanData>,
+    max_batch_size: usize,
+    batch_timeout: Duration,
+) {
+    let mut batch = Vec::with_capacity(max_batch_size);
+    let mut ticker = tokio::time::interval(batch_timeout);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ticker.tick().await; // first tick fires immediately; consume it
+
+    loop {
+        tokio::select! {
+            span = rx.recv() => match span {
+                Some(span) => {
+                    batch.push(span);
+                    if batch.len() >= max_batch_size {
+                        flush(&mut exporter, &mut batch).await;
+                    }
+                }
+                None => {
+                    flush(&mut exporter, &mut batch).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => flush(&mut exporter, &mut batch).await,
+        }
+    }
+}
+
+/// Ship a batch to the OTLP collector over gRPC.
+///
+/// Transport errors are logged (when the `logging` feature is enabled) and the batch
+/// is dropped so a flaky collector never
+/// stalls the drain task.
+async fn flush(exporter: &mut SpanExporter, batch: &mut Vec<SpanData>) {
+    if batch.is_empty() {
+        return;
+    }
+
+    if let Err(_err) = exporter.export(std::mem::take(batch)).await {
+        #[cfg(feature = "logging")]
+        slog::error!(
+            &*super::TelemetryContext::current().slog_logger(),
+            "failed to export spans to OTLP collector";
+            "error" => %_err,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustracing::sampler::AllSampler;
+    use rustracing::Tracer;
+
+    // `FinishedSpan` is `rustracing`'s own type (re-exported by `tracing::internal`,
+    // which lives outside this module), so we drive a real one directly here rather
+    // than through this crate's span/scope wrappers.
+    fn finished_span(name: &'static str) -> FinishedSpan {
+        let (tracer, span_rx) = Tracer::new(AllSampler);
+        drop(tracer.span(name).start());
+        span_rx.try_recv().expect("span should have finished")
+    }
+
+    #[test]
+    fn report_delivers_the_translated_span_to_the_exporter() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let exporter = Exporter { tx };
+
+        exporter.report(&finished_span("integration_test_span"));
+
+        let sent = rx
+            .try_recv()
+            .expect("a finished span reported to `Exporter` should reach the channel");
+        assert_eq!(sent.name.as_ref(), "integration_test_span");
+    }
+}
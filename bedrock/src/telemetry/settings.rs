@@ -0,0 +1,95 @@
+//! Settings for the telemetry subsystem.
+
+#[cfg(any(feature = "logging", feature = "tracing"))]
+use super::filter::{Directives, FilterHandle, ParseError};
+#[cfg(all(feature = "tracing", feature = "tracing-otlp"))]
+use super::otlp::OtlpSettings;
+
+/// Settings for the telemetry subsystem.
+#[derive(Clone, Debug, Default)]
+pub struct TelemetrySettings {
+    /// Settings for the `logging` subsystem.
+    #[cfg(feature = "logging")]
+    pub logging: LoggingSettings,
+
+    /// Settings for the `tracing` subsystem.
+    #[cfg(feature = "tracing")]
+    pub tracing: TracingSettings,
+}
+
+/// Settings for the `logging` subsystem.
+#[cfg(feature = "logging")]
+#[derive(Clone, Debug)]
+pub struct LoggingSettings {
+    /// `EnvFilter`-style directive string controlling per-target log verbosity, e.g.
+    /// `my_crate::db=debug,hyper=warn,info`. See [`Directives::parse`] for the
+    /// accepted syntax.
+    pub directives: String,
+}
+
+#[cfg(feature = "logging")]
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            directives: "info".into(),
+        }
+    }
+}
+
+#[cfg(feature = "logging")]
+impl LoggingSettings {
+    /// Parse [`Self::directives`] into a fresh [`FilterHandle`].
+    ///
+    /// `init` calls this once at startup, before `log::init::init` installs the
+    /// result and keeps a clone around for later [`FilterHandle::reload`] calls.
+    pub fn build_filter(&self) -> Result<FilterHandle, ParseError> {
+        Ok(FilterHandle::new(Directives::parse(&self.directives)?))
+    }
+}
+
+/// Settings for the `tracing` subsystem.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Debug)]
+pub struct TracingSettings {
+    /// `EnvFilter`-style directive string controlling per-target span verbosity.
+    /// See [`Directives::parse`] for the accepted syntax.
+    pub directives: String,
+
+    /// Which backend finished spans are reported to.
+    pub exporter: TracingExporter,
+}
+
+#[cfg(feature = "tracing")]
+impl Default for TracingSettings {
+    fn default() -> Self {
+        Self {
+            directives: "info".into(),
+            exporter: TracingExporter::default(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl TracingSettings {
+    /// Parse [`Self::directives`] into a fresh [`FilterHandle`].
+    ///
+    /// `init` calls this once at startup, before `tracing::init::init` installs the
+    /// result and keeps a clone around for later [`FilterHandle::reload`] calls.
+    pub fn build_filter(&self) -> Result<FilterHandle, ParseError> {
+        Ok(FilterHandle::new(Directives::parse(&self.directives)?))
+    }
+}
+
+/// Selects the backend that finished spans are reported to.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Debug, Default)]
+pub enum TracingExporter {
+    /// The crate's built-in `rustracing` reporter.
+    #[default]
+    Default,
+
+    /// Export spans to an OpenTelemetry-compatible collector over OTLP, instead of
+    /// the built-in reporter.
+    #[cfg(feature = "tracing-otlp")]
+    Otlp(OtlpSettings),
+}
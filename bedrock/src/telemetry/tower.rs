@@ -0,0 +1,74 @@
+//! [`tower`] integration that propagates [`TelemetryContext`] per request.
+//!
+//! [`TelemetryLayer`] wraps any [`tower::Service`] so that each inbound request is
+//! handled with a freshly forked log and its own tracing span, mirroring the
+//! `request_span`/`service_span` adapters from `tracing-tower` but driving this
+//! crate's [`TelemetryContext`] instead of the global `tracing` subscriber.
+
+use super::{TelemetryContext, WithTelemetryContext};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// [`tower::Layer`] that attaches a per-request [`TelemetryContext`] to the inner service.
+///
+/// `make_span` derives the tracing span name from each request. It's handed the
+/// request's freshly forked [`TelemetryContext`] so it can seed the forked log with
+/// request-scoped fields via [`TelemetryContext::add_log_fields`] before the request
+/// is handled.
+#[derive(Clone)]
+pub struct TelemetryLayer<M> {
+    make_span: M,
+}
+
+impl<M> TelemetryLayer<M> {
+    /// [`TODO ROCK-13`]
+    pub fn new(make_span: M) -> Self {
+        Self { make_span }
+    }
+}
+
+impl<S, M> Layer<S> for TelemetryLayer<M>
+where
+    M: Clone,
+{
+    type Service = TelemetryService<S, M>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TelemetryService {
+            inner,
+            make_span: self.make_span.clone(),
+        }
+    }
+}
+
+/// [`tower::Service`] produced by [`TelemetryLayer`].
+#[derive(Clone)]
+pub struct TelemetryService<S, M> {
+    inner: S,
+    make_span: M,
+}
+
+impl<S, M, Req> Service<Req> for TelemetryService<S, M>
+where
+    S: Service<Req>,
+    S::Future: Send + 'static,
+    M: Fn(&Req, &TelemetryContext) -> &'static str,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = WithTelemetryContext<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        // Capture the ambient context and give the request its own log so sibling
+        // requests don't share mutations, then let `make_span` seed that log with
+        // request-scoped fields before the span is opened.
+        let ctx = TelemetryContext::current().with_forked_log();
+        let span_name = (self.make_span)(&req, &ctx);
+
+        ctx.apply_with_tracing_span(span_name, self.inner.call(req))
+    }
+}